@@ -49,7 +49,17 @@ struct Field {
     packet_length: Option<String>,
     struct_length: Option<String>,
     is_payload: bool,
-    construct_with: Option<Vec<Type>>
+    construct_with: Option<Vec<Type>>,
+    /// Name of the accessor returning the discriminant used to dispatch the next layer.
+    next_header: Option<String>,
+    /// Mapping of discriminant value (as written) to the next-layer packet type name.
+    next_map: Vec<(String, String)>,
+    /// For a field whose logical type is a user enum, the primitive wire type it is stored as.
+    primitive_enum: Option<Type>,
+    /// This field holds the serialized byte length of the named field, computed on `populate`.
+    length_of: Option<String>,
+    /// This field holds the element count of the named vector field, computed on `populate`.
+    count_of: Option<String>,
 }
 
 #[derive(Clone)]
@@ -99,6 +109,11 @@ fn make_packet(ecx: &mut ExtCtxt, span: Span, name: String, sd: &ast::StructDef)
         let mut packet_length = None;
         let mut struct_length = None;
         let mut construct_with = Vec::new();
+        let mut next_header = None;
+        let mut next_map = Vec::new();
+        let mut primitive_enum = None;
+        let mut length_of = None;
+        let mut count_of = None;
         let mut seen = Vec::new();
         for attr in field.node.attrs.iter() {
             let ref node = attr.node.value.node;
@@ -139,6 +154,41 @@ fn make_packet(ecx: &mut ExtCtxt, span: Span, name: String, sd: &ast::StructDef)
                                 return None;
                             }
                         }
+                    } else if &s[..] == "primitive_enum" {
+                        if items.iter().len() != 1 {
+                            ecx.span_err(field.span, "#[primitive_enum] must specify exactly one wire type, e.g. #[primitive_enum(u16be)]");
+                            return None;
+                        }
+                        if let ast::MetaWord(ref s) = items[0].node {
+                            match make_type(s.to_string()) {
+                                Ok(ty @ Type::Primitive(..)) => primitive_enum = Some(ty),
+                                Ok(_) => {
+                                    ecx.span_err(field.span, "#[primitive_enum] wire type must be a primitive");
+                                    return None;
+                                },
+                                Err(e) => {
+                                    ecx.span_err(field.span, &e);
+                                    return None;
+                                }
+                            }
+                        } else {
+                            ecx.span_err(field.span, "#[primitive_enum] should be of the form #[primitive_enum(<wire type>)]");
+                            return None;
+                        }
+                    } else if &s[..] == "payload_map" {
+                        for item in items.iter() {
+                            if let ast::MetaNameValue(ref ty_name, ref lit) = item.node {
+                                if let ast::LitStr(ref val, _) = lit.node {
+                                    next_map.push((val.to_string(), ty_name.to_string()));
+                                } else {
+                                    ecx.span_err(field.span, "#[payload_map] values should be string literals, e.g. #[payload_map(Ipv4 = \"0x0800\")]");
+                                    return None;
+                                }
+                            } else {
+                                ecx.span_err(field.span, "#[payload_map] should be of the form #[payload_map(Type = \"discriminant\", ...)]");
+                                return None;
+                            }
+                        }
                     } else {
                         ecx.span_err(field.span, &format!("unknown attribute: {}", s)[..]);
                         return None;
@@ -156,6 +206,31 @@ fn make_packet(ecx: &mut ExtCtxt, span: Span, name: String, sd: &ast::StructDef)
                                 return None;
                             }
                         },
+                        "length_of" => {
+                            if let ast::LitStr(ref s, _) = lit.node {
+                                length_of = Some(s.to_string());
+                            } else {
+                                ecx.span_err(field.span, "#[length_of] should be used as #[length_of = \"field_name\"]");
+                                return None;
+                            }
+                        },
+                        "count_of" => {
+                            if let ast::LitStr(ref s, _) = lit.node {
+                                count_of = Some(s.to_string());
+                            } else {
+                                ecx.span_err(field.span, "#[count_of] should be used as #[count_of = \"field_name\"]");
+                                return None;
+                            }
+                        },
+                        "next_header" => {
+                            let ref node = lit.node;
+                            if let &ast::LitStr(ref s, _) = node {
+                                next_header = Some(s.to_string());
+                            } else {
+                                ecx.span_err(field.span, "#[next_header] should be used as #[next_header = \"name_of_accessor\"]");
+                                return None;
+                            }
+                        },
                         "length" => {
                             let ref node = lit.node;
                             if let &ast::LitStr(ref s, _) = node {
@@ -212,15 +287,32 @@ fn make_packet(ecx: &mut ExtCtxt, span: Span, name: String, sd: &ast::StructDef)
                 }
             },
             Type::Misc(_) => {
-                if construct_with.is_empty() {
+                if construct_with.is_empty() && primitive_enum.is_none() {
                     ecx.span_err(field.span,
-                                 "non-primitive field types must specify #[construct_with]");
+                                 "non-primitive field types must specify #[construct_with] or #[primitive_enum]");
                     return None;
                 }
             },
             _ => {}
         }
 
+        if next_header.is_some() && !is_payload {
+            ecx.span_err(field.span, "#[next_header] may only be used on the #[payload] field");
+            return None;
+        }
+
+        if (length_of.is_some() || count_of.is_some()) && match ty {
+            Type::Primitive(..) => false,
+            _ => true,
+        } {
+            ecx.span_err(field.span, "#[length_of]/#[count_of] may only be used on a primitive field");
+            return None;
+        }
+        if length_of.is_some() && count_of.is_some() {
+            ecx.span_err(field.span, "a field may not be both #[length_of] and #[count_of]");
+            return None;
+        }
+
         fields.push(Field {
             name: field_name,
             span: field.span,
@@ -229,6 +321,11 @@ fn make_packet(ecx: &mut ExtCtxt, span: Span, name: String, sd: &ast::StructDef)
             struct_length: struct_length,
             is_payload: is_payload,
             construct_with: Some(construct_with),
+            next_header: next_header,
+            next_map: next_map,
+            primitive_enum: primitive_enum,
+            length_of: length_of,
+            count_of: count_of,
         });
     }
 
@@ -375,9 +472,19 @@ impl<'a, 'b, 'c> GenContext<'a, 'b, 'c> {
 
 pub fn generate_packet(ecx: &mut ExtCtxt,
                    span: Span,
-                   _meta_item: &ast::MetaItem,
+                   meta_item: &ast::MetaItem,
                    item: &Annotatable,
                    push: &mut FnMut(Annotatable)) {
+    // Forwarded from `#[packet(checked)]` - emit fallible accessors and setters.
+    let checked = if let ast::MetaList(_, ref items) = meta_item.node {
+        items.iter().any(|item| match item.node {
+            ast::MetaWord(ref s) => &s[..] == "checked",
+            _ => false,
+        })
+    } else {
+        false
+    };
+
     if let Some(packets) = make_packets(ecx, span, item) {
         let mut cx = GenContext {
             ecx: ecx,
@@ -387,28 +494,40 @@ pub fn generate_packet(ecx: &mut ExtCtxt,
         for packet in &packets {
             generate_packet_structs(&mut cx, &packet);
 
-            if let Some((payload_bounds, packet_size)) = generate_packet_impls(&mut cx, &packet) {
+            if let Some((payload_bounds, packet_size)) = generate_packet_impls(&mut cx, &packet, checked) {
                 generate_packet_size_impls(&mut cx, &packet, &packet_size[..]);
 
                 generate_packet_trait_impls(&mut cx, &packet, &payload_bounds);
                 generate_iterables(&mut cx, &packet);
-                generate_converters(&mut cx, &packet);
+                generate_converters(&mut cx, &packet, checked);
                 generate_debug_impls(&mut cx, &packet);
+                generate_repr(&mut cx, &packet, checked);
+                generate_packet_chain(&mut cx, &packet);
+                generate_reinterpret(&mut cx, &packet);
+                generate_owned(&mut cx, &packet);
             }
         }
     }
 }
 
 fn generate_packet_structs(cx: &mut GenContext, packet: &Packet) {
-    for (name, mutable) in vec![(packet.packet_name(), ""),
-                             (packet.packet_name_mut(), " mut")] {
-        cx.push_item_from_string(format!("
-            #[derive(PartialEq)]
-            /// A structure enabling manipulation of on the wire packets
-            pub struct {}<'p> {{
-                packet: &'p{} [u8],
-            }}", name, mutable));
-    }
+    // A single struct, generic over the type of its backing storage. `T: AsRef<[u8]>`
+    // provides read access (the getters), while storage that is additionally
+    // `AsMut<[u8]>` gains the setters through a separate blanket `impl` below.
+    cx.push_item_from_string(format!("
+        #[derive(PartialEq)]
+        /// A structure enabling manipulation of on the wire packets
+        pub struct {name}<T: AsRef<[u8]>> {{
+            packet: T,
+        }}", name = packet.packet_name()));
+
+    // Type aliases kept for source compatibility with the old pair of structs.
+    cx.push_item_from_string(format!("
+        /// An immutable, borrowed view of a {base} packet
+        pub type {imm_name}Ref<'p> = {name}<&'p [u8]>;", name = packet.packet_name(), imm_name = packet.base_name, base = packet.base_name));
+    cx.push_item_from_string(format!("
+        /// A mutable, borrowed view of a {base} packet
+        pub type {mut_name}<'p> = {name}<&'p mut [u8]>;", name = packet.packet_name(), mut_name = packet.packet_name_mut(), base = packet.base_name));
 }
 
 fn handle_misc_field(cx: &mut GenContext,
@@ -488,61 +607,199 @@ fn handle_misc_field(cx: &mut GenContext,
 
 }
 
+fn handle_enum_field(field: &Field,
+                     bit_offset: &mut usize,
+                     co: &mut String,
+                     name: &String,
+                     mutators: &mut String,
+                     accessors: &mut String,
+                     enum_ty_str: &String,
+                     checked: bool) {
+    let (wire_ty, size, endianness) = match field.primitive_enum {
+        Some(Type::Primitive(ref ty_str, size, endianness)) => (ty_str.clone(), size, endianness),
+        _ => unreachable!(),
+    };
+
+    let mut ops = operations(*bit_offset % 8, size).unwrap();
+    if endianness == Endianness::Little {
+        ops = to_little_endian(ops);
+    }
+    // Inner helpers that read/write the raw discriminant, reusing the primitive codegen.
+    let inner_accessor = generate_accessor_str("raw", &wire_ty[..], &co[..], &ops[..], Some(&name[..]));
+    let inner_mutator = generate_mutator_str("raw", &wire_ty[..], &co[..], &to_mutator(&ops[..])[..], Some(&name[..]));
+    *bit_offset += size;
+
+    // Getter: read the raw integer and map it through the enum's `from_primitive`.
+    let (ret_ty, map) = if checked {
+        (format!("Result<{}, ::pnet::packet::PacketError>", enum_ty_str),
+         format!("match {enum_ty}::from_primitive(raw) {{
+                Some(val) => Ok(val),
+                None => Err(::pnet::packet::PacketError::ConstraintOutOfBounds {{
+                    field: \"{name}\",
+                    value: raw as u64,
+                }}),
+            }}", enum_ty = enum_ty_str, name = field.name))
+    } else {
+        (enum_ty_str.clone(),
+         format!("{enum_ty}::from_primitive(raw).unwrap()", enum_ty = enum_ty_str))
+    };
+    // In checked mode an unrecognised discriminant surfaces as a `PacketError`; the default
+    // mode has no error channel, so the getter panics and the doc says so.
+    let get_doc = if checked {
+        format!("/// Get the value of the {name} field", name = field.name)
+    } else {
+        format!("/// Get the value of the {name} field
+                        ///
+                        /// # Panics
+                        ///
+                        /// Panics if the on-the-wire value is not a known {enum_ty} discriminant.
+                        /// Use the `#[packet(checked)]` variant, whose getter returns a
+                        /// `Result`, to handle unrecognised values without panicking.",
+                name = field.name, enum_ty = enum_ty_str)
+    };
+    *accessors = format!("{accessors}
+                        {get_doc}
+                        #[inline]
+                        #[allow(trivial_numeric_casts)]
+                        pub fn get_{name}(&self) -> {ret_ty} {{
+                            {inner_accessor}
+
+                            let raw = get_raw(self);
+
+                            {map}
+                        }}
+                        ",
+                        accessors = accessors, name = field.name, ret_ty = ret_ty,
+                        get_doc = get_doc, inner_accessor = inner_accessor, map = map);
+
+    // Setter: write the enum's discriminant back to the wire.
+    *mutators = format!("{mutators}
+                        /// Set the value of the {name} field
+                        #[inline]
+                        #[allow(trivial_numeric_casts)]
+                        pub fn set_{name}(&mut self, val: {enum_ty}) {{
+                            use pnet::packet::PrimitiveValues;
+                            {inner_mutator}
+
+                            let raw = val.to_primitive_values().0 as {wire_ty};
+
+                            set_raw(self, raw);
+                        }}
+                        ",
+                        mutators = mutators, name = field.name, enum_ty = enum_ty_str,
+                        inner_mutator = inner_mutator, wire_ty = wire_ty);
+}
+
 fn handle_vec_primitive(cx: &mut GenContext,
                         error: &mut bool,
                         inner_ty_str: &String,
                         field: &Field,
                         accessors: &mut String,
                         mutators: &mut String,
-                        co: &mut String) {
+                        co: &mut String,
+                        checked: bool) {
     if inner_ty_str == "u8" {
         if !field.is_payload {
+            let (ret_ty, ret_val) = if checked {
+                ("Result<Vec<u8>, ::pnet::packet::PacketError>", "Ok(vec)")
+            } else {
+                ("Vec<u8>", "vec")
+            };
             *accessors = format!("{accessors}
                                     /// Get the value of the {name} field (copies contents)
                                     #[inline]
                                     #[allow(trivial_numeric_casts)]
-                                    pub fn get_{name}(&self) -> Vec<{inner_ty_str}> {{
+                                    pub fn get_{name}(&self) -> {ret_ty} {{
                                         let current_offset = {co};
                                         let len = {packet_length};
 
-                                        let packet = &self.packet[current_offset..len];
+                                        let packet = &self.packet.as_ref()[current_offset..len];
                                         let mut vec = Vec::with_capacity(packet.len());
                                         vec.push_all(packet);
 
-                                        vec
+                                        {ret_val}
                                     }}
                                     ",
                                     accessors = accessors,
                                     name = field.name,
                                     co = co,
                                     packet_length = field.packet_length.as_ref().unwrap(),
-                                    inner_ty_str = inner_ty_str);
+                                    ret_ty = ret_ty,
+                                    ret_val = ret_val);
         }
-        let check_len = if field.packet_length.is_some() {
-            format!("let len = {packet_length};
+        if checked {
+            // Replace the infallible `assert!`/copy with an explicit length check that
+            // surfaces malformed input as a structured error.
+            let check_len = if field.packet_length.is_some() {
+                format!("let len = {packet_length};
+                                    if vals.len() > len {{
+                                        return Err(::pnet::packet::PacketError::TrailingBytes);
+                                    }}
+                                    if current_offset + vals.len() > self.packet.as_ref().len() {{
+                                        return Err(::pnet::packet::PacketError::BufferTooShort {{
+                                            field: \"{name}\",
+                                            needed: current_offset + vals.len(),
+                                            got: self.packet.as_ref().len(),
+                                        }});
+                                    }}",
+                                    packet_length = field.packet_length.as_ref().unwrap(),
+                                    name = field.name)
+            } else {
+                format!("if current_offset + vals.len() > self.packet.as_ref().len() {{
+                                        return Err(::pnet::packet::PacketError::BufferTooShort {{
+                                            field: \"{name}\",
+                                            needed: current_offset + vals.len(),
+                                            got: self.packet.as_ref().len(),
+                                        }});
+                                    }}", name = field.name)
+            };
+            *mutators = format!("{mutators}
+                                /// Set the value of the {name} field (copies contents)
+                                #[inline]
+                                #[allow(trivial_numeric_casts)]
+                                pub fn set_{name}(&mut self, vals: Vec<u8>)
+                                    -> Result<(), ::pnet::packet::PacketError>
+                                {{
+                                    use std::slice::bytes::copy_memory;
+                                    let current_offset = {co};
+
+                                    {check_len}
+
+                                    copy_memory(&vals[..], &mut self.packet.as_mut()[current_offset..]);
+
+                                    Ok(())
+                                }}
+                                ",
+                                mutators = mutators,
+                                name = field.name,
+                                co = co,
+                                check_len = check_len);
+        } else {
+            let check_len = if field.packet_length.is_some() {
+                format!("let len = {packet_length};
                                              assert!(vals.len() <= len);",
                                              packet_length = field.packet_length.as_ref().unwrap())
-        } else {
-            String::new()
-        };
-        *mutators = format!("{mutators}
+            } else {
+                String::new()
+            };
+            *mutators = format!("{mutators}
                                 /// Set the value of the {name} field (copies contents)
                                 #[inline]
                                 #[allow(trivial_numeric_casts)]
-                                pub fn set_{name}(&mut self, vals: Vec<{inner_ty_str}>) {{
+                                pub fn set_{name}(&mut self, vals: Vec<u8>) {{
                                     use std::slice::bytes::copy_memory;
                                     let current_offset = {co};
 
                                     {check_len}
 
-                                    copy_memory(&vals[..], &mut self.packet[current_offset..]);
+                                    copy_memory(&vals[..], &mut self.packet.as_mut()[current_offset..]);
                                 }}
                                 ",
                                 mutators = mutators,
                                 name = field.name,
                                 co = co,
-                                check_len = check_len,
-                                inner_ty_str = inner_ty_str);
+                                check_len = check_len);
+        }
     } else {
         cx.ecx.span_err(field.span, "unimplemented variable length field");
         *error = true;
@@ -555,7 +812,8 @@ fn handle_vector_field(cx: &mut GenContext,
                        accessors: &mut String,
                        mutators: &mut String,
                        inner_ty: &Box<Type>,
-                       co: &mut String)
+                       co: &mut String,
+                       checked: bool)
 {
     if !field.is_payload && !field.packet_length.is_some() {
         cx.ecx.span_err(field.span, "variable length field must have #[length_fn = \"\"] attribute");
@@ -570,7 +828,7 @@ fn handle_vector_field(cx: &mut GenContext,
                                     let current_offset = {co};
                                     let len = {packet_length};
 
-                                    &self.packet[current_offset..len]
+                                    &self.packet.as_ref()[current_offset..len]
                                 }}
                                 ",
                                 accessors = accessors,
@@ -585,7 +843,7 @@ fn handle_vector_field(cx: &mut GenContext,
                                     let current_offset = {co};
                                     let len = {packet_length};
 
-                                    &mut self.packet[current_offset..len]
+                                    &mut self.packet.as_mut()[current_offset..len]
                                 }}
                                 ",
                                 mutators = mutators,
@@ -595,34 +853,76 @@ fn handle_vector_field(cx: &mut GenContext,
     }
     match **inner_ty {
         Type::Primitive(ref inner_ty_str, _size, _endianness) => {
-            handle_vec_primitive(cx, error, inner_ty_str, field, accessors, mutators, co)
+            handle_vec_primitive(cx, error, inner_ty_str, field, accessors, mutators, co, checked)
         },
         Type::Vector(_) => {
             cx.ecx.span_err(field.span, "variable length fields may not contain vectors");
             *error = true;
         },
         Type::Misc(ref inner_ty_str) => {
+            let (get_ret_ty, get_ret_val) = if checked {
+                (format!("Result<Vec<{0}>, ::pnet::packet::PacketError>", inner_ty_str),
+                 "Ok(vec)".to_string())
+            } else {
+                (format!("Vec<{0}>", inner_ty_str), "vec".to_string())
+            };
             *accessors = format!("{accessors}
                                 /// Get the value of the {name} field (copies contents)
                                 #[inline]
                                 #[allow(trivial_numeric_casts)]
-                                pub fn get_{name}(&self) -> Vec<{inner_ty_str}> {{
+                                pub fn get_{name}(&self) -> {get_ret_ty} {{
                                     use pnet::packet::FromPacket;
                                     let current_offset = {co};
                                     let len = {packet_length};
 
-                                    {inner_ty_str}Iterable {{
-                                        buf: &self.packet[current_offset..len]
+                                    let vec = {inner_ty_str}Iterable {{
+                                        buf: &self.packet.as_ref()[current_offset..len]
                                     }}.map(|packet| packet.from_packet())
-                                      .collect::<Vec<_>>()
+                                      .collect::<Vec<_>>();
+
+                                    {get_ret_val}
                                 }}
                                 ",
                                 accessors = accessors,
                                 name = field.name,
                                 co = co,
                                 packet_length = field.packet_length.as_ref().unwrap(),
+                                get_ret_ty = get_ret_ty,
+                                get_ret_val = get_ret_val,
                                 inner_ty_str = inner_ty_str);
-            *mutators = format!("{mutators}
+            if checked {
+                *mutators = format!("{mutators}
+                                /// Set the value of the {name} field (copies contents)
+                                #[inline]
+                                #[allow(trivial_numeric_casts)]
+                                pub fn set_{name}(&mut self, vals: Vec<{inner_ty_str}>)
+                                    -> Result<(), ::pnet::packet::PacketError>
+                                {{
+                                    use pnet::packet::PacketSize;
+                                    let mut current_offset = {co};
+                                    let len = {packet_length};
+                                    for val in vals.into_iter() {{
+                                        let mut packet = match Mutable{inner_ty_str}Packet::new_checked(&mut self.packet.as_mut()[current_offset..]) {{
+                                            Ok(packet) => packet,
+                                            Err(_) => return Err(::pnet::packet::PacketError::InvalidChildPacket {{ field: \"{name}\" }}),
+                                        }};
+                                        packet.populate(val);
+                                        current_offset += packet.packet_size();
+                                        if current_offset > len {{
+                                            return Err(::pnet::packet::PacketError::TrailingBytes);
+                                        }}
+                                    }}
+
+                                    Ok(())
+                                }}
+                                ",
+                                mutators = mutators,
+                                name = field.name,
+                                co = co,
+                                packet_length = field.packet_length.as_ref().unwrap(),
+                                inner_ty_str = inner_ty_str);
+            } else {
+                *mutators = format!("{mutators}
                                 /// Set the value of the {name} field (copies contents)
                                 #[inline]
                                 #[allow(trivial_numeric_casts)]
@@ -631,7 +931,7 @@ fn handle_vector_field(cx: &mut GenContext,
                                     let mut current_offset = {co};
                                     let len = {packet_length};
                                     for val in vals.into_iter() {{
-                                        let mut packet = Mutable{inner_ty_str}Packet::new(&mut self.packet[current_offset..]).unwrap();
+                                        let mut packet = Mutable{inner_ty_str}Packet::new(&mut self.packet.as_mut()[current_offset..]);
                                         packet.populate(val);
                                         current_offset += packet.packet_size();
                                         assert!(current_offset <= len);
@@ -643,13 +943,149 @@ fn handle_vector_field(cx: &mut GenContext,
                                 co = co,
                                 packet_length = field.packet_length.as_ref().unwrap(),
                                 inner_ty_str = inner_ty_str);
+            }
+        }
+    }
+}
+
+/// One field of a consecutive run of `Type::Primitive` fields sharing a byte-aligned backing
+/// region, which can be read and written as a single native-width word instead of byte-by-byte.
+struct ChunkField {
+    name: String,
+    ty_str: String,
+    size: usize,
+    endianness: Endianness,
+    /// Absolute bit offset of this field from the start of the packet.
+    start_bit: usize,
+}
+
+/// The unsigned integer type wide enough to hold a chunk of `bits` bits.
+fn chunk_word_ty(bits: usize) -> &'static str {
+    match bits {
+        8 => "u8",
+        16 => "u16",
+        24 | 32 => "u32",
+        64 => "u64",
+        _ => "u64",
+    }
+}
+
+/// Build the expression that loads `width` bytes starting at byte `start` into a `wty` word,
+/// in the requested endianness.
+fn build_word_read(start: usize, width: usize, wty: &str, big_endian: bool) -> String {
+    let mut terms = Vec::new();
+    for i in 0..width {
+        let byte = format!("(self_.packet.as_ref()[{} + {}] as {})", start, i, wty);
+        let shift = if big_endian { 8 * (width - 1 - i) } else { 8 * i };
+        if shift == 0 {
+            terms.push(byte);
+        } else {
+            terms.push(format!("({} << {})", byte, shift));
         }
     }
+    terms.connect(" | ")
+}
+
+/// Build the statements that write `word` back into `width` bytes starting at byte `start`.
+fn build_word_write(start: usize, width: usize, big_endian: bool) -> String {
+    let mut stmts = String::new();
+    for i in 0..width {
+        let shift = if big_endian { 8 * (width - 1 - i) } else { 8 * i };
+        let shifted = if shift == 0 { "word".to_string() } else { format!("(word >> {})", shift) };
+        stmts = stmts + &format!("self_.packet.as_mut()[{} + {}] = ({} & 0xff) as u8;\n",
+                                 start, i, shifted)[..];
+    }
+    stmts
 }
 
-fn generate_packet_impl(cx: &mut GenContext, packet: &Packet, mutable: bool, name: String)
+/// Emit word-based accessors and mutators for a chunk that can be read/written as one word.
+fn generate_word_chunk(chunk: &[ChunkField],
+                       total_bits: usize,
+                       accessors: &mut String,
+                       mutators: &mut String) {
+    let start = chunk[0].start_bit / 8;
+    let width = total_bits / 8;
+    let wty = chunk_word_ty(total_bits);
+    let big_endian = chunk[0].endianness == Endianness::Big;
+    let read = build_word_read(start, width, wty, big_endian);
+    let write = build_word_write(start, width, big_endian);
+
+    let mut cum = 0;
+    for field in chunk {
+        // Bits below the field: from the low end of the word to the low end of the field
+        // (fields are laid out from the word's high end).
+        let shift = total_bits - cum - field.size;
+        let mask: u64 = if field.size >= 64 { !0 } else { (1u64 << field.size) - 1 };
+
+        accessors.push_str(&format!("/// Get the {name} field
+        #[inline]
+        #[allow(trivial_numeric_casts)]
+        pub fn get_{name}(&self) -> {ty} {{
+            let self_ = self;
+            let word = {read} as {wty};
+
+            ((word >> {shift}) & {mask}) as {ty}
+        }}
+        ", name = field.name, ty = field.ty_str, read = read, wty = wty,
+           shift = shift, mask = mask)[..]);
+
+        mutators.push_str(&format!("/// Set the {name} field
+        #[inline]
+        #[allow(trivial_numeric_casts)]
+        pub fn set_{name}(&mut self, val: {ty}) {{
+            let self_ = self;
+            let mask = {mask} as {wty};
+            let word = {read} as {wty};
+            let word = (word & !(mask << {shift})) | (((val as {wty}) & mask) << {shift});
+
+            {write}
+        }}
+        ", name = field.name, ty = field.ty_str, read = read, write = write,
+           wty = wty, shift = shift, mask = mask)[..]);
+
+        cum += field.size;
+    }
+}
+
+/// Flush a pending chunk of primitive fields. If the chunk is a whole number of bytes, fits a
+/// native width (8/16/24/32/64), and is big-endian (or a single field), it is emitted as one
+/// word read/write; otherwise each field keeps the per-byte accessor path.
+fn flush_chunk(chunk: &mut Vec<ChunkField>, accessors: &mut String, mutators: &mut String) {
+    if chunk.is_empty() {
+        return;
+    }
+
+    let total_bits: usize = chunk.iter().map(|c| c.size).fold(0, |a, b| a + b);
+    let byte_aligned = chunk[0].start_bit % 8 == 0 && total_bits % 8 == 0;
+    let native = match total_bits { 8 | 16 | 24 | 32 | 64 => true, _ => false };
+    let homogeneous = chunk.iter().all(|c| c.endianness == chunk[0].endianness);
+    let word_ok = byte_aligned && native && homogeneous
+                  && (chunk[0].endianness == Endianness::Big || chunk.len() == 1);
+
+    if word_ok {
+        generate_word_chunk(&chunk[..], total_bits, accessors, mutators);
+    } else {
+        // Fall back to the per-byte path, reproducing the original per-field codegen.
+        for field in chunk.iter() {
+            let mut ops = operations(field.start_bit % 8, field.size).unwrap();
+            if field.endianness == Endianness::Little {
+                ops = to_little_endian(ops);
+            }
+            let co = (field.start_bit / 8).to_string();
+            mutators.push_str(&generate_mutator_str(&field.name[..], &field.ty_str[..], &co[..],
+                                                     &to_mutator(&ops[..])[..], None)[..]);
+            accessors.push_str(&generate_accessor_str(&field.name[..], &field.ty_str[..], &co[..],
+                                                       &ops[..], None)[..]);
+        }
+    }
+
+    chunk.clear();
+}
+
+fn generate_packet_impl(cx: &mut GenContext, packet: &Packet, checked: bool)
     -> Option<(PayloadBounds, String)>
 {
+    let name = packet.packet_name();
     let mut bit_offset = 0;
     let mut offset_fns_packet = Vec::new();
     let mut offset_fns_struct = Vec::new();
@@ -657,9 +1093,24 @@ fn generate_packet_impl(cx: &mut GenContext, packet: &Packet, mutable: bool, nam
     let mut mutators = "".to_string();
     let mut error = false;
     let mut payload_bounds = None;
+    // Recursion emitted into `check_len`: one block per `Vec<SubPacket>` field that walks the
+    // field's region and validates every sub-packet via its own `new_checked`/`check_len`.
+    let mut check_len_children = String::new();
+    // Pending run of consecutive primitive fields to be coalesced into word accesses. Only
+    // collected while the offset is still statically known (before any variable-length field).
+    let mut chunk: Vec<ChunkField> = Vec::new();
     for (idx, ref field) in packet.fields.iter().enumerate() {
         let mut co = current_offset(bit_offset, &offset_fns_packet[..]);
 
+        // Anything that is not a chunkable primitive flushes the pending chunk first.
+        let chunkable = offset_fns_packet.is_empty() && match field.ty {
+            Type::Primitive(..) => true,
+            _ => false,
+        };
+        if !chunkable {
+            flush_chunk(&mut chunk, &mut accessors, &mut mutators);
+        }
+
         if field.is_payload {
             let mut upper_bound_str = "".to_string();
             if field.packet_length.is_some() {
@@ -679,6 +1130,21 @@ fn generate_packet_impl(cx: &mut GenContext, packet: &Packet, mutable: bool, nam
             });
         }
         match field.ty {
+            Type::Primitive(ref ty_str, size, endianness) if chunkable => {
+                // Buffer the field; flush as soon as the chunk lands on a byte boundary.
+                chunk.push(ChunkField {
+                    name: field.name.clone(),
+                    ty_str: ty_str.clone(),
+                    size: size,
+                    endianness: endianness,
+                    start_bit: bit_offset,
+                });
+                bit_offset += size;
+                let chunk_bits: usize = chunk.iter().map(|c| c.size).fold(0, |a, b| a + b);
+                if chunk_bits % 8 == 0 {
+                    flush_chunk(&mut chunk, &mut accessors, &mut mutators);
+                }
+            },
             Type::Primitive(ref ty_str, size, endianness) => {
                 let mut ops = operations(bit_offset % 8, size).unwrap();
 
@@ -692,11 +1158,36 @@ fn generate_packet_impl(cx: &mut GenContext, packet: &Packet, mutable: bool, nam
                 bit_offset += size;
             },
             Type::Vector(ref inner_ty) => {
-                handle_vector_field(cx, &mut error, &field, &mut accessors, &mut mutators, inner_ty, &mut co)
+                // Sub-packet vectors are validated recursively: walk the field's region and run
+                // each element's `new_checked` (which calls its own `check_len`).
+                if let Type::Misc(ref inner_ty_str) = **inner_ty {
+                    check_len_children = check_len_children + &format!("
+            {{
+                use pnet::packet::PacketSize;
+                let current_offset = {co};
+                let end = {end};
+                let mut buf = &self.packet.as_ref()[current_offset..end];
+                while buf.len() > 0 {{
+                    let child = match {inner}Packet::new_checked(buf) {{
+                        Ok(child) => child,
+                        Err(_) => return Err(::pnet::packet::PacketError::InvalidChildPacket {{ field: \"{name}\" }}),
+                    }};
+                    buf = &buf[child.packet_size()..];
+                }}
+            }}",
+                        co = co, end = field.packet_length.as_ref().unwrap(),
+                        inner = inner_ty_str, name = field.name)[..];
+                }
+                handle_vector_field(cx, &mut error, &field, &mut accessors, &mut mutators, inner_ty, &mut co, checked)
             },
             Type::Misc(ref ty_str) => {
-                handle_misc_field(cx, &mut error, &field, &mut bit_offset, &offset_fns_packet[..],
-                                  &mut co, &name, &mut mutators, &mut accessors, &ty_str)
+                if field.primitive_enum.is_some() {
+                    handle_enum_field(&field, &mut bit_offset, &mut co, &name, &mut mutators,
+                                      &mut accessors, &ty_str, checked)
+                } else {
+                    handle_misc_field(cx, &mut error, &field, &mut bit_offset, &offset_fns_packet[..],
+                                      &mut co, &name, &mut mutators, &mut accessors, &ty_str)
+                }
             }
         }
         if field.packet_length.is_some() {
@@ -706,32 +1197,74 @@ fn generate_packet_impl(cx: &mut GenContext, packet: &Packet, mutable: bool, nam
             offset_fns_struct.push(field.struct_length.as_ref().unwrap().clone());
         }
     }
+    // Flush any primitive run left over at the end of the packet.
+    flush_chunk(&mut chunk, &mut accessors, &mut mutators);
 
     if error {
         return None;
     }
 
-    fn generate_set_fields(packet: &Packet) -> String {
+    fn generate_set_fields(packet: &Packet, checked: bool) -> String {
+        // The measured serialized byte length of a referenced field, used by #[length_of].
+        fn measured_len(packet: &Packet, target: &str) -> String {
+            match packet.fields.iter().find(|f| f.name == target) {
+                Some(&Field { ty: Type::Vector(ref inner), .. }) => match **inner {
+                    Type::Misc(ref inner_ty_str) => format!(
+                        "packet.{target}.iter().map(|x| {inner}Packet::<&[u8]>::packet_size(x)).fold(0, |a, b| a + b)",
+                        target = target, inner = inner_ty_str),
+                    _ => format!("packet.{target}.len()", target = target),
+                },
+                // Payload (Vec<u8>) and any other field: byte length of its serialized form.
+                _ => format!("packet.{target}.len()", target = target),
+            }
+        }
+
         let mut set_fields = String::new();
         for field in packet.fields.iter() {
-            set_fields = set_fields + &format!("self.set_{field}(packet.{field});\n",
-            field = field.name)[..];
+            let ty_str = match field.ty {
+                Type::Primitive(ref ty_str, _, _) => Some(ty_str.clone()),
+                _ => None,
+            };
+
+            // Length/count fields are derived from the data they describe rather than copied
+            // from the input struct, keeping them correct-by-construction.
+            if let Some(ref target) = field.count_of {
+                set_fields = set_fields + &format!("self.set_{field}(packet.{target}.len() as {ty});\n",
+                    field = field.name, target = target, ty = ty_str.as_ref().unwrap())[..];
+                continue;
+            }
+            if let Some(ref target) = field.length_of {
+                set_fields = set_fields + &format!("self.set_{field}(({len}) as {ty});\n",
+                    field = field.name, len = measured_len(packet, target),
+                    ty = ty_str.as_ref().unwrap())[..];
+                continue;
+            }
 
+            // In checked mode the variable-length setters are fallible; `populate` assumes a
+            // correctly sized buffer, so it unwraps them.
+            let fallible = checked && match field.ty {
+                Type::Vector(_) => true,
+                _ => false,
+            };
+            if fallible {
+                set_fields = set_fields + &format!("self.set_{field}(packet.{field}).unwrap();\n",
+                field = field.name)[..];
+            } else {
+                set_fields = set_fields + &format!("self.set_{field}(packet.{field});\n",
+                field = field.name)[..];
+            }
         }
 
         set_fields
     }
 
-    let populate = if mutable {
-        let set_fields = generate_set_fields(&packet);
-        let imm_name = packet.packet_name();
-        format!("/// Populates a {name}Packet using a {name} structure
+    let populate = {
+        let set_fields = generate_set_fields(&packet, checked);
+        format!("/// Populates a {name}Packet using a {base_name} structure
              #[inline]
-             pub fn populate(&mut self, packet: {name}) {{
+             pub fn populate(&mut self, packet: {base_name}) {{
                  {set_fields}
-             }}", name = &imm_name[..imm_name.len() - 6], set_fields = set_fields)
-    } else {
-        "".to_string()
+             }}", name = packet.base_name, base_name = packet.base_name, set_fields = set_fields)
     };
 
     // If there are no variable length fields defined, then `_packet` is not used, hence
@@ -752,24 +1285,65 @@ fn generate_packet_impl(cx: &mut GenContext, packet: &Packet, mutable: bool, nam
         (bit_offset / 8) + 1
     };
 
-    cx.push_item_from_string(format!("impl<'a> {name}<'a> {{
-        /// Constructs a new {name}. If the provided buffer is less than the minimum required
-        /// packet size, this will return None.
+    // The total length (in bytes) the packet occupies, including every variable-length
+    // and payload region computed from the already-parsed length fields.
+    let packet_length = current_offset(bit_offset, &offset_fns_packet[..]);
+
+    // Read-only surface: available for any backing storage that can be viewed as a byte slice.
+    cx.push_item_from_string(format!("impl<T: AsRef<[u8]>> {name}<T> {{
+        /// Constructs a new {name}.
+        ///
+        /// This does *not* validate that the provided buffer is large enough to hold every
+        /// field - it simply wraps it. It is intended for the construction of outgoing
+        /// packets, where the buffer comes from a transmit ring and is about to be
+        /// overwritten. Use {name}::new_checked to validate an incoming buffer instead.
         #[inline]
-        pub fn new<'p>(packet: &'p {mut} [u8]) -> Option<{name}<'p>> {{
-            if packet.len() >= {name}::minimum_packet_size() {{
-                Some({name} {{ packet: packet }})
-            }} else {{
-                None
-            }}
+        pub fn new(packet: T) -> {name}<T> {{
+            {name} {{ packet: packet }}
+        }}
+
+        /// Constructs a new {name}, validating that the backing buffer is large enough to
+        /// hold every field. If it is not, the error from check_len is returned.
+        #[inline]
+        pub fn new_checked(packet: T) -> Result<{name}<T>, ::pnet::packet::PacketError> {{
+            let _self = {name} {{ packet: packet }};
+            try!(_self.check_len());
+
+            Ok(_self)
         }}
 
-        /// Maps from a {name} to a {imm_name}
+        /// Verifies that the backing buffer is large enough to hold the fixed header plus
+        /// every variable-length and payload region described by this packet's length fields,
+        /// recursing into each nested sub-packet's own check_len so a sub-packet whose length
+        /// fields overrun its slice is rejected too. Returns {{BufferTooShort}} for a buffer that is
+        /// too short, and {{InvalidChildPacket}} for a nested sub-packet that fails its own check.
         #[inline]
-        pub fn to_immutable<'p>(&'p self) -> {imm_name}<'p> {{
-            match *self {{
-                {name} {{ ref packet }} => {imm_name} {{ packet: packet }}
+        pub fn check_len(&self) -> Result<(), ::pnet::packet::PacketError> {{
+            let min = {name}::<T>::minimum_packet_size();
+            if self.packet.as_ref().len() < min {{
+                return Err(::pnet::packet::PacketError::BufferTooShort {{
+                    field: \"{base_name}\",
+                    needed: min,
+                    got: self.packet.as_ref().len(),
+                }});
             }}
+            let needed = {packet_length};
+            if self.packet.as_ref().len() < needed {{
+                return Err(::pnet::packet::PacketError::BufferTooShort {{
+                    field: \"{base_name}\",
+                    needed: needed,
+                    got: self.packet.as_ref().len(),
+                }});
+            }}
+            {check_len_children}
+
+            Ok(())
+        }}
+
+        /// Maps from a {name} to an immutable, borrowed view of the same packet.
+        #[inline]
+        pub fn to_immutable(&self) -> {name}<&[u8]> {{
+            {name} {{ packet: self.packet.as_ref() }}
         }}
 
         /// The minimum size (in bytes) a packet of this type can be. It's based on the total size
@@ -781,81 +1355,84 @@ fn generate_packet_impl(cx: &mut GenContext, packet: &Packet, mutable: bool, nam
 
         {packet_size_struct}
 
-        {populate}
-
         {accessors}
-
-        {mutators}
     }}", name = name,
-    imm_name = packet.packet_name(),
-    mut = if mutable { "mut" } else { "" },
+    base_name = packet.base_name,
+    packet_length = packet_length,
     byte_size = byte_size,
     accessors = accessors,
-    mutators = if mutable { &mutators[..] } else { "" },
+    packet_size_struct = packet_size_struct,
+    check_len_children = check_len_children
+        ));
+
+    // Mutating surface: blanket impl gated on storage that can also be viewed mutably.
+    cx.push_item_from_string(format!("impl<T: AsRef<[u8]> + AsMut<[u8]>> {name}<T> {{
+        {populate}
+
+        {mutators}
+    }}", name = name,
     populate = populate,
-    packet_size_struct = packet_size_struct
+    mutators = mutators
         ));
 
-    Some((payload_bounds.unwrap(), current_offset(bit_offset, &offset_fns_packet[..])))
+    Some((payload_bounds.unwrap(), packet_length))
 }
 
 
-fn generate_packet_impls(cx: &mut GenContext, packet: &Packet) -> Option<(PayloadBounds, String)> {
-    let mut ret = None;
-    for (mutable, name) in vec![(false, packet.packet_name()),
-                                (true, packet.packet_name_mut())] {
-        ret = generate_packet_impl(cx, packet, mutable, name);
-    }
-
-    ret
+fn generate_packet_impls(cx: &mut GenContext, packet: &Packet, checked: bool)
+    -> Option<(PayloadBounds, String)>
+{
+    generate_packet_impl(cx, packet, checked)
 }
 
 fn generate_packet_size_impls(cx: &mut GenContext, packet: &Packet, size: &str) {
-    for name in &[packet.packet_name(), packet.packet_name_mut()] {
-        cx.push_item_from_string(format!("
-            impl<'a> ::pnet::packet::PacketSize for {name}<'a> {{
-                fn packet_size(&self) -> usize {{
-                    {size}
-                }}
+    cx.push_item_from_string(format!("
+        impl<T: AsRef<[u8]>> ::pnet::packet::PacketSize for {name}<T> {{
+            fn packet_size(&self) -> usize {{
+                {size}
             }}
-        ", name = name, size = size));
-    }
+        }}
+    ", name = packet.packet_name(), size = size));
 }
 
 fn generate_packet_trait_impls(cx: &mut GenContext, packet: &Packet, payload_bounds: &PayloadBounds) {
-    for (name, mutable, u_mut, mut_) in vec![
-        (packet.packet_name_mut(), "Mutable", "_mut", "mut"),
-        (packet.packet_name_mut(), "", "", ""),
-        (packet.packet_name(), "", "", "")
-    ] {
-        let mut pre = "".to_string();
-        let mut start = "".to_string();
-        let mut end = "".to_string();
-        if payload_bounds.lower.len() > 0 {
-            pre = pre + &format!("let start = {};", payload_bounds.lower)[..];
-            start = "start".to_string();
-        }
-        if payload_bounds.upper.len() > 0 {
-            pre = pre + &format!("let end = {};", payload_bounds.upper)[..];
-            end = "end".to_string();
-        }
-        cx.push_item_from_string(format!("impl<'a> ::pnet::packet::{mutable}Packet for {name}<'a> {{
-            #[inline]
-            fn packet{u_mut}<'p>(&'p {mut_} self) -> &'p {mut_} [u8] {{ &{mut_} self.packet[..] }}
-
-            #[inline]
-            fn payload{u_mut}<'p>(&'p {mut_} self) -> &'p {mut_} [u8] {{
-                {pre}
-                &{mut_} self.packet[{start}..{end}]
-            }}
-        }}", name = name,
-             start = start,
-             end = end,
-             pre = pre,
-             mutable = mutable,
-             u_mut = u_mut,
-             mut_ = mut_));
+    let name = packet.packet_name();
+
+    let mut pre = "".to_string();
+    let mut start = "".to_string();
+    let mut end = "".to_string();
+    if payload_bounds.lower.len() > 0 {
+        pre = pre + &format!("let start = {};", payload_bounds.lower)[..];
+        start = "start".to_string();
+    }
+    if payload_bounds.upper.len() > 0 {
+        pre = pre + &format!("let end = {};", payload_bounds.upper)[..];
+        end = "end".to_string();
     }
+
+    // `Packet` is available for any readable storage.
+    cx.push_item_from_string(format!("impl<T: AsRef<[u8]>> ::pnet::packet::Packet for {name}<T> {{
+        #[inline]
+        fn packet<'p>(&'p self) -> &'p [u8] {{ &self.packet.as_ref()[..] }}
+
+        #[inline]
+        fn payload<'p>(&'p self) -> &'p [u8] {{
+            {pre}
+            &self.packet.as_ref()[{start}..{end}]
+        }}
+    }}", name = name, start = start, end = end, pre = pre));
+
+    // `MutablePacket` is gated on storage that can be viewed mutably.
+    cx.push_item_from_string(format!("impl<T: AsRef<[u8]> + AsMut<[u8]>> ::pnet::packet::MutablePacket for {name}<T> {{
+        #[inline]
+        fn packet_mut<'p>(&'p mut self) -> &'p mut [u8] {{ &mut self.packet.as_mut()[..] }}
+
+        #[inline]
+        fn payload_mut<'p>(&'p mut self) -> &'p mut [u8] {{
+            {pre}
+            &mut self.packet.as_mut()[{start}..{end}]
+        }}
+    }}", name = name, start = start, end = end, pre = pre));
 }
 
 fn generate_iterables(cx: &mut GenContext, packet: &Packet) {
@@ -870,12 +1447,12 @@ fn generate_iterables(cx: &mut GenContext, packet: &Packet) {
 
     cx.push_item_from_string(format!("
     impl<'a> Iterator for {name}Iterable<'a> {{
-        type Item = {name}Packet<'a>;
+        type Item = {name}Packet<&'a [u8]>;
 
-        fn next(&mut self) -> Option<{name}Packet<'a>> {{
+        fn next(&mut self) -> Option<{name}Packet<&'a [u8]>> {{
             use pnet::packet::PacketSize;
             if self.buf.len() > 0 {{
-                let ret = {name}Packet::new(self.buf).unwrap();
+                let ret = {name}Packet::new(self.buf);
                 self.buf = &self.buf[ret.packet_size()..];
 
                 return Some(ret);
@@ -891,22 +1468,20 @@ fn generate_iterables(cx: &mut GenContext, packet: &Packet) {
     ", name = name));
 }
 
-fn generate_converters(cx: &mut GenContext, packet: &Packet) {
-    let get_fields = generate_get_fields(packet);
+fn generate_converters(cx: &mut GenContext, packet: &Packet, checked: bool) {
+    let get_fields = generate_get_fields(packet, checked);
 
-    for name in &[packet.packet_name(), packet.packet_name_mut()] {
-        cx.push_item_from_string(format!("
-        impl<'p> ::pnet::packet::FromPacket for {packet}<'p> {{
-            type T = {name};
-            #[inline]
-            fn from_packet(&self) -> {name} {{
-                use pnet::packet::Packet;
-                {name} {{
-                    {get_fields}
-                }}
+    cx.push_item_from_string(format!("
+    impl<T: AsRef<[u8]>> ::pnet::packet::FromPacket for {packet}<T> {{
+        type T = {name};
+        #[inline]
+        fn from_packet(&self) -> {name} {{
+            use pnet::packet::Packet;
+            {name} {{
+                {get_fields}
             }}
-        }}", packet = name, name = packet.base_name, get_fields = get_fields));
-    }
+        }}
+    }}", packet = packet.packet_name(), name = packet.base_name, get_fields = get_fields));
 }
 
 fn generate_debug_impls(cx: &mut GenContext, packet: &Packet) {
@@ -921,19 +1496,292 @@ fn generate_debug_impls(cx: &mut GenContext, packet: &Packet) {
         }
     }
 
-    for packet in &[packet.packet_name(), packet.packet_name_mut()] {
-        cx.push_item_from_string(format!("
-        impl<'p> ::std::fmt::Debug for {packet}<'p> {{
-            fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {{
-                write!(fmt,
-                       \"{packet} {{{{ {field_fmt_str} }}}}\"
-                       {get_fields}
-                )
+    cx.push_item_from_string(format!("
+    impl<T: AsRef<[u8]>> ::std::fmt::Debug for {packet}<T> {{
+        fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {{
+            write!(fmt,
+                   \"{packet} {{{{ {field_fmt_str} }}}}\"
+                   {get_fields}
+            )
+        }}
+    }}", packet = packet.packet_name(), field_fmt_str = field_fmt_str, get_fields = get_fields));
+}
+
+/// Generate the low-overhead "reinterpret" helpers.
+///
+/// These hand the payload region backing this packet to another generated packet type's
+/// `new_checked`, without copying, so that layered packets can be built and rebuilt inside a
+/// single allocation. The immutable helper shares the buffer immutably, while the mutable one
+/// preserves mutability so the reinterpreted packet can be written to in place.
+fn generate_reinterpret(cx: &mut GenContext, packet: &Packet) {
+    cx.push_item_from_string(format!("
+        impl<T: AsRef<[u8]>> {name}<T> {{
+            /// Reinterpret this packet's payload region as another packet type, sharing the
+            /// same buffer without copying. `ctor` is typically another generated
+            /// `new_checked`, e.g. `packet.reinterpret_payload(Ipv4Packet::new_checked)`.
+            #[inline]
+            pub fn reinterpret_payload<'p, P, F>(&'p self, ctor: F) -> Option<P>
+                where F: FnOnce(&'p [u8]) -> Result<P, ::pnet::packet::PacketError>
+            {{
+                use pnet::packet::Packet;
+                ctor(self.payload()).ok()
+            }}
+        }}", name = packet.packet_name()));
+
+    cx.push_item_from_string(format!("
+        impl<T: AsRef<[u8]> + AsMut<[u8]>> {name}<T> {{
+            /// Reinterpret this packet's payload region as another, mutable, packet type in
+            /// place, preserving mutability so the result can be written to the shared buffer.
+            #[inline]
+            pub fn reinterpret_payload_mut<'p, P, F>(&'p mut self, ctor: F) -> Option<P>
+                where F: FnOnce(&'p mut [u8]) -> Result<P, ::pnet::packet::PacketError>
+            {{
+                use pnet::packet::MutablePacket;
+                ctor(self.payload_mut()).ok()
             }}
-        }}", packet = packet, field_fmt_str = field_fmt_str, get_fields = get_fields));
+        }}", name = packet.packet_name()));
+}
+
+/// Generate owned-buffer construction helpers for a packet.
+///
+/// Unlike {Base}Packet::new, which borrows a caller-supplied buffer, these allocate a `Vec<u8>`
+/// sized to exactly hold the given {Base} - the fixed fields plus the measured length of every
+/// variable-length field - then run the usual populate logic over it. This is the natural entry
+/// point when serialising an owned structure with nowhere to put it yet, as opposed to writing
+/// into a transmit ring.
+fn generate_owned(cx: &mut GenContext, packet: &Packet) {
+    // The serialized byte length: the fixed header plus the measured size of every
+    // variable-length and payload field. `packet_size` can't be reused here because for a
+    // `Vec<SubPacket>` field its `struct_length` is the element *count*, not the serialized
+    // byte size, so relying on it would under-allocate; sum each sub-packet's `packet_size`
+    // instead, matching `Repr::buffer_len` and `#[length_of]`.
+    let mut variable_len = String::new();
+    for field in packet.fields.iter() {
+        if let Type::Vector(ref inner) = field.ty {
+            match **inner {
+                Type::Primitive(..) => {
+                    variable_len = variable_len + &format!(" + base.{name}.len()",
+                                                           name = field.name)[..];
+                },
+                Type::Misc(ref inner_ty_str) => {
+                    variable_len = variable_len + &format!(
+                        " + base.{name}.iter().map(|x| {inner}Packet::<&[u8]>::packet_size(x)).fold(0, |a, b| a + b)",
+                        name = field.name, inner = inner_ty_str)[..];
+                },
+                Type::Vector(_) => {},
+            }
+        }
+    }
+
+    cx.push_item_from_string(format!("
+        impl {name}<Vec<u8>> {{
+            /// Construct a {name} backed by a freshly allocated buffer holding `base`.
+            ///
+            /// The buffer is sized to the fixed header plus the measured serialized length of
+            /// every variable-length and payload field, so it is exactly large enough and never
+            /// reallocates while populating.
+            pub fn owned(base: {base_name}) -> {name}<Vec<u8>> {{
+                let len = {name}::<&[u8]>::minimum_packet_size(){variable_len};
+                let mut packet = {name}::new(vec![0u8; len]);
+                packet.populate(base);
+
+                packet
+            }}
+
+            /// Serialise `base` into a freshly allocated byte buffer and return it.
+            pub fn to_bytes(base: &{base_name}) -> Vec<u8> {{
+                {name}::owned(base.clone()).packet
+            }}
+        }}", name = packet.packet_name(), base_name = packet.base_name,
+             variable_len = variable_len));
+}
+
+/// Generate the packet-chain dissection surface for a packet whose `#[payload]` field carries
+/// a `#[next_header]` discriminant.
+///
+/// Given a registered `#[payload_map(..)]`, emit a `{Base}Payload` enum with one variant per
+/// registered next-layer packet type (plus an `Unknown` catch-all), and a `next` method which
+/// reads the named discriminant accessor, slices the payload, and wraps it in the matching
+/// packet type via `new_checked`. This lets callers walk a whole protocol stack without
+/// manually chaining `new` on `payload()` at each layer.
+fn generate_packet_chain(cx: &mut GenContext, packet: &Packet) {
+    let payload_field = match packet.fields.iter().find(|f| f.is_payload) {
+        Some(field) => field,
+        None => return,
+    };
+    let next_header = match payload_field.next_header {
+        Some(ref accessor) => accessor.clone(),
+        None => return,
+    };
+
+    let enum_name = format!("{}Payload", packet.base_name);
+
+    let mut variants = String::new();
+    let mut arms = String::new();
+    for &(ref value, ref ty_name) in &payload_field.next_map {
+        variants = variants + &format!("    /// The payload parsed as a {ty}.\n    {ty}({ty}Packet<&'p [u8]>),\n",
+                                       ty = ty_name)[..];
+        arms = arms + &format!(
+            "            {value} => match {ty}Packet::new_checked(payload) {{
+                Ok(p) => {enum_name}::{ty}(p),
+                Err(_) => {enum_name}::Unknown(payload),
+            }},\n",
+            value = value, ty = ty_name, enum_name = enum_name)[..];
+    }
+
+    cx.push_item_from_string(format!("
+        /// The decapsulated payload of a {base} packet, dispatched on its {accessor}.
+        pub enum {enum_name}<'p> {{
+        {variants}    /// The payload could not be dispatched to a known next-layer type.
+            Unknown(&'p [u8]),
+        }}", base = packet.base_name, accessor = next_header, enum_name = enum_name, variants = variants));
+
+    cx.push_item_from_string(format!("
+        impl<T: AsRef<[u8]>> {packet}<T> {{
+            /// Decapsulate the payload, dispatching on the {accessor} discriminant to the
+            /// registered next-layer packet type.
+            #[inline]
+            pub fn next<'p>(&'p self) -> {enum_name}<'p> {{
+                use pnet::packet::Packet;
+                let payload = self.payload();
+                match self.{accessor}() {{
+{arms}                _ => {enum_name}::Unknown(payload),
+                }}
+            }}
+        }}",
+        packet = packet.packet_name(),
+        accessor = next_header,
+        enum_name = enum_name,
+        arms = arms));
+}
+
+/// Return the host-level Rust type a field is represented as in the owned `Repr` struct.
+fn repr_ty_str(ty: &Type) -> String {
+    match *ty {
+        Type::Primitive(ref ty_str, _, _) => ty_str.clone(),
+        Type::Vector(ref inner) => format!("Vec<{}>", repr_ty_str(inner)),
+        Type::Misc(ref ty_str) => ty_str.clone(),
     }
 }
 
+/// Generate an owned, high-level `Repr` for the packet.
+///
+/// Unlike the zero-copy accessors - which are ideal for poking individual fields - the `Repr`
+/// holds every field as a host-level value, giving a validate-once / construct-once path:
+/// `parse` reads an existing packet into owned values, `buffer_len` reports how many bytes the
+/// values will serialize to, and `emit` writes them all into a mutable packet.
+fn generate_repr(cx: &mut GenContext, packet: &Packet, checked: bool) {
+    let repr_name = format!("{}Repr", packet.base_name);
+
+    let mut struct_fields = String::new();
+    let mut parse_fields = String::new();
+    let mut emit_fields = String::new();
+    let mut variable_len = String::new();
+
+    for field in &packet.fields {
+        struct_fields = struct_fields + &format!("    /// {name}\n    pub {name}: {ty},\n",
+                                                 name = field.name,
+                                                 ty = repr_ty_str(&field.ty))[..];
+
+        if field.is_payload {
+            parse_fields = parse_fields + &format!(
+                "            {name}: {{
+                    use pnet::packet::Packet;
+                    packet.payload().to_vec()
+                }},\n", name = field.name)[..];
+            variable_len = variable_len + &format!(" + self.{name}.len()", name = field.name)[..];
+        } else {
+            // Variable-length and enum getters are fallible in checked mode; `check_len` has
+            // already validated the buffer above, so the variable-length getters do not fail
+            // here. An enum field holding an unrecognised discriminant makes the unwrap panic.
+            let fallible = checked && (field.primitive_enum.is_some() || match field.ty {
+                Type::Vector(_) => true,
+                _ => false,
+            });
+            if fallible {
+                parse_fields = parse_fields + &format!("            {name}: packet.get_{name}().unwrap(),\n",
+                                                       name = field.name)[..];
+            } else {
+                parse_fields = parse_fields + &format!("            {name}: packet.get_{name}(),\n",
+                                                       name = field.name)[..];
+            }
+        }
+
+        // Variable-length (non-payload) fields contribute their measured serialized size.
+        if let Type::Vector(ref inner) = field.ty {
+            if !field.is_payload {
+                match **inner {
+                    Type::Primitive(..) => {
+                        variable_len = variable_len + &format!(" + self.{name}.len()",
+                                                               name = field.name)[..];
+                    },
+                    Type::Misc(ref inner_ty_str) => {
+                        variable_len = variable_len + &format!(
+                            " + self.{name}.iter().map(|x| {inner}Packet::<&[u8]>::packet_size(x)).fold(0, |a, b| a + b)",
+                            name = field.name, inner = inner_ty_str)[..];
+                    },
+                    Type::Vector(_) => {},
+                }
+            }
+        }
+
+        // Variable-length setters are fallible in checked mode; `emit` targets a buffer sized
+        // by `buffer_len`, so it unwraps them.
+        let fallible = checked && match field.ty {
+            Type::Vector(_) => true,
+            _ => false,
+        };
+        if fallible {
+            emit_fields = emit_fields + &format!("        packet.set_{name}(self.{name}.clone()).unwrap();\n",
+                                                 name = field.name)[..];
+        } else {
+            emit_fields = emit_fields + &format!("        packet.set_{name}(self.{name}.clone());\n",
+                                                 name = field.name)[..];
+        }
+    }
+
+    cx.push_item_from_string(format!("
+        #[derive(Clone, Debug, PartialEq)]
+        /// An owned, high-level representation of a {base} packet.
+        pub struct {repr_name} {{
+        {struct_fields}
+        }}", base = packet.base_name, repr_name = repr_name, struct_fields = struct_fields));
+
+    cx.push_item_from_string(format!("
+        impl {repr_name} {{
+            /// Parse a {packet} into an owned {repr_name}, reading every field into a
+            /// host-level value. The backing buffer is validated via `check_len` first.
+            #[inline]
+            pub fn parse<T: AsRef<[u8]>>(packet: &{packet}<T>)
+                -> Result<{repr_name}, ::pnet::packet::PacketError>
+            {{
+                try!(packet.check_len());
+
+                Ok({repr_name} {{
+{parse_fields}
+                }})
+            }}
+
+            /// The number of bytes required to serialize this {repr_name}: the fixed header
+            /// size plus the measured size of every variable-length and payload field.
+            #[inline]
+            pub fn buffer_len(&self) -> usize {{
+                {packet}::<&[u8]>::minimum_packet_size(){variable_len}
+            }}
+
+            /// Write every field of this {repr_name} into the provided mutable packet.
+            #[inline]
+            pub fn emit<T: AsRef<[u8]> + AsMut<[u8]>>(&self, packet: &mut {packet}<T>) {{
+{emit_fields}
+            }}
+        }}",
+        repr_name = repr_name,
+        packet = packet.packet_name(),
+        parse_fields = parse_fields,
+        variable_len = variable_len,
+        emit_fields = emit_fields));
+}
+
 /// Given a type in the form `u([0-9]+)(be|le)?`, return a tuple of it's size and endianness
 ///
 /// If 1 <= size <= 8, Endianness will be Big.
@@ -979,7 +1827,7 @@ fn test_parse_ty() {
 fn generate_sop_strings(offset: &str, operations: &[SetOperation]) -> String {
     let mut op_strings = String::new();
     for (idx, sop) in operations.iter().enumerate() {
-        let pkt_replace = format!("self_.packet[{} + {}]", offset, idx);
+        let pkt_replace = format!("self_.packet.as_mut()[{} + {}]", offset, idx);
         let val_replace = "val";
         let sop = sop.to_string().replace("{packet}", &pkt_replace[..])
                                  .replace("{val}", val_replace);
@@ -1001,7 +1849,7 @@ fn generate_mutator_str(name: &str,
     let mutator = if let Some(struct_name) = inner {
         format!("#[inline]
     #[allow(trivial_numeric_casts)]
-    fn set_{name}(self_: &mut {struct_name}, val: {ty}) {{
+    fn set_{name}<T: AsRef<[u8]> + AsMut<[u8]>>(self_: &mut {struct_name}<T>, val: {ty}) {{
         {operations}
     }}", struct_name = struct_name, name = name, ty = ty, operations = op_strings)
     } else {
@@ -1038,12 +1886,12 @@ fn generate_accessor_str(name: &str,
     }
 
     let op_strings = if operations.len() == 1 {
-        let replacement_str = format!("(self_.packet[{}] as {})", offset, ty);
+        let replacement_str = format!("(self_.packet.as_ref()[{}] as {})", offset, ty);
         operations.first().unwrap().to_string().replace("{}", &replacement_str[..])
     } else {
         let mut op_strings = "".to_string();
         for (idx, operation) in operations.iter().enumerate() {
-            let replacement_str = format!("(self_.packet[{} + {}] as {})", offset, idx, ty);
+            let replacement_str = format!("(self_.packet.as_ref()[{} + {}] as {})", offset, idx, ty);
             let operation = operation.to_string().replace("{}", &replacement_str[..]);
             op_strings = op_strings + &format!("let b{} = ({}) as {};\n", idx, operation, ty)[..];
         }
@@ -1055,7 +1903,7 @@ fn generate_accessor_str(name: &str,
     let accessor = if let Some(struct_name) = inner {
         format!("#[inline]
         #[allow(trivial_numeric_casts)]
-        fn get_{name}(self_: &{struct_name}) -> {ty} {{
+        fn get_{name}<T: AsRef<[u8]>>(self_: &{struct_name}<T>) -> {ty} {{
             {operations}
         }}", struct_name = struct_name, name = name, ty = ty, operations = op_strings)
     } else {
@@ -1079,7 +1927,7 @@ fn current_offset(bit_offset: usize, offset_fns: &[String]) -> String {
     })
 }
 
-fn generate_get_fields(packet: &Packet) -> String {
+fn generate_get_fields(packet: &Packet, checked: bool) -> String {
     let mut gets = String::new();
 
     for field in &packet.fields {
@@ -1092,7 +1940,17 @@ fn generate_get_fields(packet: &Packet) -> String {
                                                 vec
                                             }},\n", field = field.name)[..]
         } else {
-            gets = gets + &format!("{field} : self.get_{field}(),\n", field = field.name)[..]
+            // In checked mode the variable-length and enum getters are fallible; `from_packet`
+            // is infallible, so it unwraps them.
+            let fallible = checked && (field.primitive_enum.is_some() || match field.ty {
+                Type::Vector(_) => true,
+                _ => false,
+            });
+            if fallible {
+                gets = gets + &format!("{field} : self.get_{field}().unwrap(),\n", field = field.name)[..]
+            } else {
+                gets = gets + &format!("{field} : self.get_{field}(),\n", field = field.name)[..]
+            }
         }
     }
 
@@ -47,27 +47,28 @@
 //! source of each of the packet types in the `pnet::packet` module. Things generated include
 //! (assuming the `Example` struct from above):
 //!
-//!  * An `ExamplePacket<'p>` structure, which is used for receiving packets on the network.
-//!    This structure contains:
-//!      - A method, `pub fn new<'p>(packet: &'p [u8]) -> ExamplePacket<'p>`, used for the
-//!        construction of an `ExamplePacket`, given a buffer to store it. The buffer should be
-//!        long enough to contain all the fields in the packet.
-//!      - A method, `pub fn to_immutable<'p>(&'p self) -> ExamplePacket<'p>`, which is simply an
-//!        identity function. It exists for consistency with `MutableExamplePacket`.
+//!  * An `ExamplePacket<T>` structure, generic over the type of its backing storage
+//!    (`T: AsRef<[u8]>`). A single type now serves both the receive and transmit paths: the
+//!    getters are available for any `T`, while the setters and `MutablePacket` impl are added
+//!    by a blanket `impl` bounded on `T: AsMut<[u8]>`. The name `ExamplePacket` is retained for
+//!    source compatibility - existing code that wrote `ExamplePacket` now names the generic type
+//!    directly. Two borrowed-buffer aliases are also provided: `ExampleRef = ExamplePacket<&[u8]>`
+//!    for the immutable view and `MutableExamplePacket = ExamplePacket<&mut [u8]>` for the mutable
+//!    one. This structure contains:
+//!      - A method, `pub fn new(packet: T) -> ExamplePacket<T>`, which simply wraps
+//!        the provided buffer without validating its length. This is intended for constructing
+//!        outgoing packets, where the buffer is about to be overwritten.
+//!      - A method, `pub fn new_checked(packet: T) -> Result<ExamplePacket<T>,
+//!        PacketError>`, which validates that the buffer is long enough to contain all the
+//!        fields in the packet (via `check_len`) before wrapping it. This should be used when
+//!        parsing incoming packets.
+//!      - A method, `pub fn to_immutable(&self) -> ExamplePacket<&[u8]>`, which borrows the
+//!        backing buffer immutably.
 //!      - A number of accessor methods, of the form `pub get_{field_name}(&self) -> {field_type}`,
 //!        which will retreive the host representation of the on-the-wire value.
-//!  * A `MutableExamplePacket<'p>` structure, which is used when sending packets on the network.
-//!    This structure contains:
-//!      - A method, `pub fn new<'p>(packet: &'p mut [u8]) -> MutableExamplePacket<'p>`, used for
-//!        the construction of a `MutableExamplePacket`, given a buffer to store it. The buffer
-//!        should be long enough to contain all the fields in the packet.
-//!      - A method, `pub fn to_immutable<'p>(&'p self) -> ExamplePacket<'p>`, which converts from
-//!        a `MutableExamplePacket` to an `ExamplePacket`
+//!  * When `T: AsMut<[u8]>` (e.g. `MutableExamplePacket`), the same type additionally gains:
 //!      - A method, `pub fn populate(&mut self, packet: Example)`, which, given an `Example`
-//!        struct, will populate the `MutableExamplePacket` with the values from the `Example`
-//!        struct.
-//!      - A number of accessor methods, of the form `pub get_{field_name}(&self) -> {field_type}`,
-//!        which will retreive the host representation of the on-the-wire value.
+//!        struct, will populate the packet with the values from the `Example` struct.
 //!      - A number of mutator methods, of the form `pub set_{field_name}(&mut self,
 //!        val: {field_type})`, which will take a host value, convert it to the required
 //!        on-the-wire format, and store it in the buffer which backs the `MutableExamplePacket`.
@@ -81,6 +82,19 @@
 //!  * An `ExampleIterator` structure, which implements `std::iter::Iterator`, to allow iterating
 //!    over vectors of `ExamplePacket` contained within another packet. Used internally.
 //!
+//! ## Checked mode
+//!
+//! By default the variable-length setters panic when the values do not fit: an `assert!`
+//! checks them against the field's length, a buffer that is too short panics on the copy, and
+//! child packets are wrapped with the non-validating `new`. Annotating a packet with `#[packet(checked)]`
+//! switches to fallible codegen instead: the variable-length/iterable getters become
+//! `get_{field}() -> Result<T, PacketError>` and the copy-in setters become
+//! `set_{field}(..) -> Result<(), PacketError>`, returning a structured
+//! `pnet::packet::PacketError` (`BufferTooShort`, `TrailingBytes`, `InvalidChildPacket`, ..)
+//! rather than aborting. This lets code parsing untrusted wire data, e.g. from a raw socket,
+//! handle malformed packets gracefully. The convenience conversions (`from_packet`, `populate`
+//! and the `Repr` parse/emit path) assume a correctly sized buffer and unwrap internally.
+//!
 //! ## Attributes
 //!
 //! There are a number of attributes which fields may have, these include:
@@ -91,8 +105,9 @@
 //!    it should have the type `Vec<T>`. It must have the `#[length_fn]` (or #[length]) attribute,
 //!    which specifies a function name to calculate the length of the field. The signature for the
 //!    length function should be
-//!    `fn {function_name}<'a>(example_packet: &ExamplePacket<'a>) -> usize`, substituting
-//!    `&ExamplePacket<'a>` for the appropriately named packet type for your structure. You may
+//!    `fn {function_name}(example_packet: &ExamplePacket<&[u8]>) -> usize`, substituting
+//!    `&ExamplePacket<&[u8]>` (equivalently `&ExampleRef`) for the appropriately named packet
+//!    type for your structure. You may
 //!    access whichever fields are required to calculate the length of the field. The returned
 //!    value should be a number of bytes that the field uses.
 //!
@@ -123,6 +138,38 @@
 //!    If the packet has no payload, you must still specify this attribute, but you can provide a
 //!    `#[length_fn]` attribute returning zero.
 //!
+//!  * \#[next_header = "accessor_name"] / \#[payload_map(Type = "discriminant", ...)]
+//!
+//!    These attributes enable automatic packet-chain decapsulation. They may only be placed on
+//!    the `#[payload]` field. `#[next_header]` names an accessor returning a discriminant value
+//!    (for example `#[next_header = "get_ethertype"]`), and `#[payload_map]` registers a
+//!    mapping from discriminant values to other `#[packet]` types, for example
+//!    `#[payload_map(Ipv4 = "0x0800", Arp = "0x0806")]`. A `{Name}Payload` enum and a
+//!    `pub fn next(&self) -> {Name}Payload` method are then generated: `next` reads the
+//!    discriminant, slices the payload, and wraps it (via `new_checked`) in the registered
+//!    next-layer packet, falling back to `{Name}Payload::Unknown(&[u8])` for unknown values.
+//!
+//!  * \#[length_of = "field_name"] / \#[count_of = "field_name"]
+//!
+//!    These attributes mark a primitive field as derived: rather than being copied from the
+//!    input struct, its value is computed during `populate` from the data it describes.
+//!    `#[length_of = "payload"]` writes the serialized byte length of the named field (for a
+//!    `Vec` of sub-packets, the sum of their `packet_size`s), and `#[count_of = "options"]`
+//!    writes the element count of the named vector field. This keeps header length/count fields
+//!    from drifting out of sync with the payload or vector they describe.
+//!
+//!  * \#[primitive_enum(<wire type>)]
+//!
+//!    This attribute is used for a field whose logical type is a user enum but whose on-the-wire
+//!    representation is a single unsigned integer, for example an IP protocol number or an
+//!    Ethernet ethertype. The field is declared with the enum type, and the attribute gives the
+//!    wire type, for example `#[primitive_enum(u16be)] ethertype: EtherType`. The enum must
+//!    provide a `from_primitive(<wire type>) -> Option<Self>` associated function and implement
+//!    `pnet::packet::PrimitiveValues` to convert back to the wire value. The generated
+//!    `get_{field}` reads the raw integer and maps it through `from_primitive`; in checked mode
+//!    an unrecognised discriminant yields a `PacketError::ConstraintOutOfBounds` rather than
+//!    silently producing garbage.
+//!
 //!  * \#[construct_with(<primitive type>, ...)]
 //!
 //!    Unfortunately, compiler plugins do not currently have access to type information during the
@@ -167,13 +214,28 @@ pub mod types;
 /// #[_packet_lint], which is used to trigger linting.
 fn packet_modifier(ecx: &mut ExtCtxt,
                    _span: Span,
-                   _meta_item: &ast::MetaItem,
+                   meta_item: &ast::MetaItem,
                    item: Annotatable) -> Annotatable {
     let item = item.expect_item();
     let mut new_item = (*item).clone();
 
+    // `#[packet(checked)]` opts in to fallible accessors and setters. The flag is forwarded to
+    // the generator via the internal `#[_packet_generator(checked)]` attribute.
+    let checked = if let ast::MetaList(_, ref items) = meta_item.node {
+        items.iter().any(|item| match item.node {
+            ast::MetaWord(ref s) => &s[..] == "checked",
+            _ => false,
+        })
+    } else {
+        false
+    };
+
     new_item.attrs.push(quote_attr!(ecx, #[_packet_lint]));
-    new_item.attrs.push(quote_attr!(ecx, #[_packet_generator]));
+    if checked {
+        new_item.attrs.push(quote_attr!(ecx, #[_packet_generator(checked)]));
+    } else {
+        new_item.attrs.push(quote_attr!(ecx, #[_packet_generator]));
+    }
     new_item.attrs.push(quote_attr!(ecx, #[derive(Clone, Debug)]));
     new_item.attrs.push(quote_attr!(ecx, #[allow(unused_attributes)]));
 